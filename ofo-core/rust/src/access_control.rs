@@ -0,0 +1,98 @@
+/*!
+ * Rust Access Control
+ * Role-based permission model gating consent-sensitive lease grants
+ */
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+pub type RoleId = String;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserData {
+    pub id: String,
+    pub roles: Vec<RoleId>,
+}
+
+/// A permission requested by a caller, e.g. `lease.device_control`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Permission(pub String);
+
+impl Permission {
+    pub fn for_lease_type(lease_type: &str) -> Self {
+        Self(format!("lease.{}", lease_type))
+    }
+}
+
+/// A rule granting a single permission, carried by a `Role`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermRule {
+    pub permission: String,
+}
+
+impl PermRule {
+    pub fn matches(&self, perm: &Permission) -> bool {
+        self.permission == perm.0
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Role {
+    pub id: RoleId,
+    pub permissions: Vec<PermRule>,
+    /// Roles this role inherits permissions from.
+    pub parents: Vec<RoleId>,
+}
+
+/// Role-based access control: resolves a user's role set (including
+/// inherited parent roles) and checks whether it carries a given
+/// permission.
+pub struct AccessControl {
+    roles: HashMap<RoleId, Role>,
+}
+
+impl AccessControl {
+    pub fn new() -> Self {
+        Self {
+            roles: HashMap::new(),
+        }
+    }
+
+    pub fn register_role(&mut self, role: Role) {
+        self.roles.insert(role.id.clone(), role);
+    }
+
+    /// Recursively walks `role` and its parent chain into `acc`, skipping
+    /// roles already present so a parent cycle can't recurse forever.
+    pub fn tally_role(&self, acc: &mut HashMap<RoleId, Role>, role: &RoleId) {
+        if acc.contains_key(role) {
+            return;
+        }
+
+        let Some(resolved) = self.roles.get(role) else {
+            debug!("⚠️ Unknown role referenced during tally: {}", role);
+            return;
+        };
+
+        acc.insert(role.clone(), resolved.clone());
+
+        for parent in &resolved.parents {
+            self.tally_role(acc, parent);
+        }
+    }
+
+    /// True if any role tallied from `user.roles` (including inherited
+    /// parent roles) carries a rule matching `perm`.
+    pub fn check(&self, user: &UserData, perm: &Permission) -> bool {
+        let mut tallied: HashMap<RoleId, Role> = HashMap::new();
+
+        for role in &user.roles {
+            self.tally_role(&mut tallied, role);
+        }
+
+        tallied
+            .values()
+            .any(|role| role.permissions.iter().any(|rule| rule.matches(perm)))
+    }
+}
@@ -0,0 +1,143 @@
+/*!
+ * Admin API
+ * HTTP surface mirroring `EVARustCore` so operators and orchestration
+ * tooling can drive the safety core over the network instead of only at
+ * process startup.
+ */
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use tokio::time::Duration;
+use tracing::{error, info};
+
+use crate::access_control::UserData;
+use crate::EVARustCore;
+
+#[derive(Clone)]
+struct AdminApiState {
+    core: Arc<EVARustCore>,
+    bearer_token: String,
+}
+
+/// Structured error body returned for every non-2xx admin API response.
+#[derive(Debug, Serialize)]
+struct ApiError {
+    error: String,
+}
+
+impl ApiError {
+    fn response(status: StatusCode, message: impl Into<String>) -> Response {
+        (status, Json(ApiError { error: message.into() })).into_response()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LeaseRequest {
+    lease_type: String,
+    duration_secs: Option<u64>,
+    user_id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct LeaseResponse {
+    lease_id: String,
+}
+
+/// Role granted to any caller who clears `require_bearer`. The admin API
+/// has no per-caller identity store, so the bearer token is the only
+/// authentication it has - every authenticated caller is treated as an
+/// operator rather than trusting a client-supplied role list, which would
+/// let an unauthenticated caller self-grant RBAC permissions.
+const AUTHENTICATED_ROLE: &str = "operator";
+
+/// Checks the destructive-endpoint bearer token. Non-destructive reads
+/// (`GET /status`, `GET /workers`) don't need this. `POST /leases` does:
+/// it's still RBAC/policy-gated by `can_grant_lease`, but the caller's
+/// roles come from `AUTHENTICATED_ROLE`, not the request body, so the
+/// bearer check is what stands between an anonymous caller and a
+/// consent-gated lease.
+fn require_bearer(state: &AdminApiState, headers: &HeaderMap) -> Result<(), Response> {
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match provided {
+        Some(token) if token == state.bearer_token => Ok(()),
+        _ => Err(ApiError::response(StatusCode::UNAUTHORIZED, "missing or invalid bearer token")),
+    }
+}
+
+async fn post_leases(State(state): State<AdminApiState>, headers: HeaderMap, Json(req): Json<LeaseRequest>) -> Response {
+    if let Err(resp) = require_bearer(&state, &headers) {
+        return resp;
+    }
+
+    let user = UserData { id: req.user_id, roles: vec![AUTHENTICATED_ROLE.to_string()] };
+    let duration = req.duration_secs.map(Duration::from_secs);
+
+    match state.core.request_lease(&req.lease_type, duration, &user).await {
+        Some(lease_id) => (StatusCode::CREATED, Json(LeaseResponse { lease_id })).into_response(),
+        None => ApiError::response(StatusCode::FORBIDDEN, "lease request denied by policy"),
+    }
+}
+
+async fn delete_leases(State(state): State<AdminApiState>, headers: HeaderMap) -> Response {
+    if let Err(resp) = require_bearer(&state, &headers) {
+        return resp;
+    }
+
+    state.core.revoke_all_leases().await;
+    StatusCode::NO_CONTENT.into_response()
+}
+
+async fn get_status(State(state): State<AdminApiState>) -> Response {
+    Json(state.core.get_system_status().await).into_response()
+}
+
+async fn post_emergency_stop(State(state): State<AdminApiState>, headers: HeaderMap) -> Response {
+    if let Err(resp) = require_bearer(&state, &headers) {
+        return resp;
+    }
+
+    match state.core.emergency_stop().await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => ApiError::response(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+async fn get_workers(State(state): State<AdminApiState>) -> Response {
+    Json(state.core.list_workers().await).into_response()
+}
+
+fn router(core: Arc<EVARustCore>, bearer_token: String) -> Router {
+    let state = AdminApiState { core, bearer_token };
+
+    Router::new()
+        .route("/leases", post(post_leases).delete(delete_leases))
+        .route("/status", get(get_status))
+        .route("/emergency-stop", post(post_emergency_stop))
+        .route("/workers", get(get_workers))
+        .with_state(state)
+}
+
+/// Binds the admin API router to `addr` and serves it for the lifetime of
+/// the returned task.
+pub fn spawn(core: Arc<EVARustCore>, bearer_token: String, addr: SocketAddr) -> tokio::task::JoinHandle<()> {
+    let app = router(core, bearer_token);
+
+    tokio::spawn(async move {
+        info!("🛠️ Admin API listening on http://{}", addr);
+
+        if let Err(e) = axum::Server::bind(&addr).serve(app.into_make_service()).await {
+            error!("admin API server error: {}", e);
+        }
+    })
+}
@@ -8,12 +8,19 @@ use std::sync::Arc;
 use tokio::time::{Duration, Instant};
 use tracing::{info, warn, error};
 
+use crate::metrics::Metrics;
+use crate::quorum_auth::{QuorumAuth, QuorumResult, Share};
+
 #[derive(Debug)]
 pub struct EmergencyKillSwitch {
     activated: Arc<AtomicBool>,
     emergency_timeout: Duration,
     last_safety_check: Arc<tokio::sync::RwLock<Instant>>,
     safety_violations: Arc<tokio::sync::RwLock<Vec<SafetyViolation>>>,
+    // m-of-n operator authorization for `reset`. `None` means reset stays
+    // unilateral (the original, less safe, behavior).
+    quorum_auth: Option<QuorumAuth>,
+    metrics: Metrics,
 }
 
 #[derive(Debug, Clone)]
@@ -39,6 +46,82 @@ impl EmergencyKillSwitch {
             emergency_timeout,
             last_safety_check: Arc::new(tokio::sync::RwLock::new(Instant::now())),
             safety_violations: Arc::new(tokio::sync::RwLock::new(Vec::new())),
+            quorum_auth: None,
+            metrics: Metrics::new(),
+        }
+    }
+
+    pub fn set_metrics(&mut self, metrics: Metrics) {
+        self.metrics = metrics;
+    }
+
+    /// Configures a threshold `k` of `n` operator shares that
+    /// `submit_reset_share` collects before resetting the switch. Returns
+    /// the `n` shares to hand out to operators; only `H(secret)` is
+    /// retained here. `reset` does not check this quorum, so a direct call
+    /// to it still resets the switch without any shares.
+    pub fn setup_quorum_reset(&mut self, threshold: usize, total_shares: usize) -> Vec<Share> {
+        let (quorum, shares) = QuorumAuth::generate(threshold, total_shares);
+        self.quorum_auth = Some(quorum);
+        info!("🔐 Quorum reset authorization configured: {}-of-{}", threshold, total_shares);
+        shares
+    }
+
+    /// Like `setup_quorum_reset`, but restores a commitment captured from a
+    /// prior run (see `QuorumAuth::commitment_hex`) instead of generating a
+    /// fresh secret. Used at startup to restore quorum reset authorization
+    /// from `EVAConfig`'s `emergency_secret_commitment` without invalidating
+    /// shares operators were already handed.
+    pub fn configure_quorum_from_commitment(
+        &mut self,
+        threshold: usize,
+        total_shares: usize,
+        commitment_hex: &str,
+    ) -> Result<(), String> {
+        let quorum = QuorumAuth::from_commitment_hex(threshold, total_shares, commitment_hex)?;
+        self.quorum_auth = Some(quorum);
+        info!(
+            "🔐 Quorum reset authorization restored from commitment: {}-of-{}",
+            threshold, total_shares
+        );
+        Ok(())
+    }
+
+    /// Buffers an operator's reset share. Once enough distinct shares are
+    /// collected, attempts the Lagrange reconstruction; a match resets the
+    /// switch, a mismatch records a critical `SafetyViolation` (a forged or
+    /// corrupted share) and clears the buffer.
+    pub async fn submit_reset_share(
+        &mut self,
+        operator_id: u64,
+        share: Share,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let Some(quorum) = &mut self.quorum_auth else {
+            return Err("quorum reset authorization is not configured".into());
+        };
+
+        match quorum.submit_reset_share(operator_id, share) {
+            QuorumResult::Pending(count) => {
+                info!("🔐 Received reset share {}/{}", count, quorum.threshold());
+                Ok(false)
+            }
+            QuorumResult::Authorized => {
+                info!("✅ Quorum reconstructed the reset secret; resetting");
+                self.reset().await?;
+                Ok(true)
+            }
+            QuorumResult::Mismatch => {
+                self.report_violation(
+                    "quorum_reconstruction_mismatch".to_string(),
+                    ViolationSeverity::Critical,
+                    format!(
+                        "Reset share quorum reconstructed a secret that didn't match the commitment \
+                         (possible forged share from operator {})",
+                        operator_id
+                    ),
+                ).await;
+                Ok(false)
+            }
         }
     }
 
@@ -53,7 +136,8 @@ impl EmergencyKillSwitch {
         
         // Update safety check timestamp
         *self.last_safety_check.write().await = Instant::now();
-        
+        self.metrics.set_kill_switch_activated(false);
+
         info!("✅ Emergency Kill Switch initialized and armed");
         Ok(())
     }
@@ -62,7 +146,8 @@ impl EmergencyKillSwitch {
         warn!("🚨 EMERGENCY KILL SWITCH ACTIVATED!");
         
         self.activated.store(true, Ordering::SeqCst);
-        
+        self.metrics.set_kill_switch_activated(true);
+
         // Record activation
         let violation = SafetyViolation {
             timestamp: Instant::now(),
@@ -117,7 +202,8 @@ impl EmergencyKillSwitch {
         };
 
         self.safety_violations.write().await.push(violation);
-        
+        self.metrics.record_safety_violation(&severity);
+
         match severity {
             ViolationSeverity::Critical => error!("🚨 CRITICAL SAFETY VIOLATION: {}", description),
             ViolationSeverity::High => warn!("⚠️ High severity violation: {}", description),
@@ -149,7 +235,8 @@ impl EmergencyKillSwitch {
         self.activated.store(false, Ordering::SeqCst);
         self.safety_violations.write().await.clear();
         *self.last_safety_check.write().await = Instant::now();
-        
+        self.metrics.set_kill_switch_activated(false);
+
         info!("✅ Emergency Kill Switch reset and re-armed");
         Ok(())
     }
@@ -165,4 +252,67 @@ impl EmergencyKillSwitch {
         info!("✅ Emergency Kill Switch shutdown complete");
         Ok(())
     }
+}
+
+/// Deterministic timing tests for the critical-violation and safety-check
+/// auto-triggers in `should_emergency_stop`. Rather than a hand-rolled
+/// `Clock` trait, these lean on `tokio::time`'s own virtual-clock support
+/// (`start_paused` + `advance`): `Instant::now()` already reads from that
+/// virtual clock, so every `elapsed()` check in this file sees it for
+/// free, with no production code changes and no real sleeps in the test
+/// run.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn auto_triggers_at_exactly_the_third_critical_violation() {
+        let mut switch = EmergencyKillSwitch::new(Duration::from_secs(5));
+        switch.initialize().await.unwrap();
+
+        for i in 0..2 {
+            switch.report_violation(
+                "test_violation".to_string(),
+                ViolationSeverity::Critical,
+                format!("synthetic violation {}", i),
+            ).await;
+            assert!(!switch.should_emergency_stop().await);
+        }
+
+        switch.report_violation(
+            "test_violation".to_string(),
+            ViolationSeverity::Critical,
+            "synthetic violation 2".to_string(),
+        ).await;
+
+        assert!(switch.should_emergency_stop().await);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn safety_check_timeout_fires_deterministically() {
+        let emergency_timeout = Duration::from_secs(5);
+        let mut switch = EmergencyKillSwitch::new(emergency_timeout);
+        switch.initialize().await.unwrap();
+
+        assert!(!switch.should_emergency_stop().await);
+
+        tokio::time::advance(emergency_timeout * 2 + Duration::from_millis(1)).await;
+
+        assert!(switch.should_emergency_stop().await);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn update_safety_check_resets_the_timeout() {
+        let emergency_timeout = Duration::from_secs(5);
+        let mut switch = EmergencyKillSwitch::new(emergency_timeout);
+        switch.initialize().await.unwrap();
+
+        tokio::time::advance(emergency_timeout).await;
+        switch.update_safety_check().await;
+        // 11s since `initialize` (past the 2x timeout), but only 6s since
+        // the update, so only a reset timer keeps the switch quiet.
+        tokio::time::advance(emergency_timeout + Duration::from_secs(1)).await;
+
+        assert!(!switch.should_emergency_stop().await);
+    }
 }
\ No newline at end of file
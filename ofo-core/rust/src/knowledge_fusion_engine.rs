@@ -3,11 +3,17 @@
  * High-performance knowledge processing and pattern recognition
  */
 
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::time::SystemTime;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use tokio::time::Instant;
 use tracing::{info, debug, warn};
 
+use crate::knowledge_store::KnowledgeStore;
+use crate::metrics::Metrics;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KnowledgeNode {
     pub id: String,
@@ -17,6 +23,17 @@ pub struct KnowledgeNode {
     pub created_at: Instant,
     pub connections: Vec<String>,
     pub access_count: u64,
+    /// Wall-clock timestamp of the most recent mutation to this node's
+    /// scalar fields (`content`/`confidence`/`source`) or its tombstone
+    /// state. `created_at`'s `Instant` is monotonic and not comparable or
+    /// serializable across processes, so CRDT merges use this as the
+    /// last-writer-wins key instead.
+    pub updated_at_wall: SystemTime,
+    /// Once set, this node is considered deleted. It stays in the graph
+    /// (rather than being removed outright) so a tombstone with a newer
+    /// `updated_at_wall` can suppress a stale peer resurrecting the same
+    /// id via `merge_delta`.
+    pub tombstoned: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -34,6 +51,8 @@ pub struct RustKnowledgeFusion {
     pattern_discoveries: u64,
     confidence_threshold: f64,
     max_connections_per_node: usize,
+    metrics: Metrics,
+    store: Option<Box<dyn KnowledgeStore>>,
 }
 
 impl RustKnowledgeFusion {
@@ -44,15 +63,39 @@ impl RustKnowledgeFusion {
             pattern_discoveries: 0,
             confidence_threshold: 0.7,
             max_connections_per_node: 10,
+            metrics: Metrics::new(),
+            store: None,
         }
     }
 
+    pub fn set_metrics(&mut self, metrics: Metrics) {
+        self.metrics = metrics;
+    }
+
+    pub fn set_store(&mut self, store: Box<dyn KnowledgeStore>) {
+        self.store = Some(store);
+    }
+
     pub async fn initialize(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         info!("🧠 Initializing Rust Knowledge Fusion Engine...");
-        
-        self.initialize_base_knowledge().await?;
-        
-        info!("✅ Rust Knowledge Fusion Engine initialized with {} nodes", 
+
+        if let Some(store) = &self.store {
+            match store.load_all().await {
+                Ok(nodes) => {
+                    info!("💾 Reloaded {} knowledge nodes from durable storage", nodes.len());
+                    for node in nodes {
+                        self.knowledge_graph.insert(node.id.clone(), node);
+                    }
+                }
+                Err(e) => warn!("⚠️ Failed to reload knowledge graph from storage: {}", e),
+            }
+        }
+
+        if self.knowledge_graph.is_empty() {
+            self.initialize_base_knowledge().await?;
+        }
+
+        info!("✅ Rust Knowledge Fusion Engine initialized with {} nodes",
               self.knowledge_graph.len());
         Ok(())
     }
@@ -108,7 +151,7 @@ impl RustKnowledgeFusion {
         Ok(())
     }
 
-    pub async fn add_knowledge_node(&mut self, id: String, content: HashMap<String, serde_json::Value>, 
+    pub async fn add_knowledge_node(&mut self, id: String, content: HashMap<String, serde_json::Value>,
                                    confidence: f64, source: String) -> String {
         let node = KnowledgeNode {
             id: id.clone(),
@@ -118,14 +161,120 @@ impl RustKnowledgeFusion {
             created_at: Instant::now(),
             connections: Vec::new(),
             access_count: 0,
+            updated_at_wall: SystemTime::now(),
+            tombstoned: false,
         };
 
+        self.persist_node(&node).await;
         self.knowledge_graph.insert(id.clone(), node);
         debug!("📝 Added knowledge node: {}", id);
-        
+
         id
     }
 
+    /// Tombstones a node rather than removing it outright, so the
+    /// deletion itself can propagate through `export_delta`/`merge_delta`
+    /// and suppress a stale peer resurrecting the same id.
+    pub async fn delete_knowledge_node(&mut self, id: &str) -> bool {
+        let Some(node) = self.knowledge_graph.get_mut(id) else {
+            return false;
+        };
+
+        node.tombstoned = true;
+        node.updated_at_wall = SystemTime::now();
+        let persisted = node.clone();
+
+        self.persist_node(&persisted).await;
+        debug!("🪦 Tombstoned knowledge node: {}", id);
+        true
+    }
+
+    async fn persist_node(&self, node: &KnowledgeNode) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.persist(node).await {
+                warn!("⚠️ Failed to persist knowledge node {}: {}", node.id, e);
+            }
+        }
+    }
+
+    /// Deterministic fingerprint of a node's scalar fields, used only to
+    /// break `updated_at_wall` ties in `merge_node`. `content` is canonicalized
+    /// through a `BTreeMap` first since `HashMap`'s iteration order isn't
+    /// stable across processes, so two replicas holding equal content
+    /// always compute the same fingerprint.
+    fn scalar_fingerprint(node: &KnowledgeNode) -> Vec<u8> {
+        let canonical_content: BTreeMap<&String, &serde_json::Value> = node.content.iter().collect();
+
+        let mut hasher = Sha256::new();
+        hasher.update(serde_json::to_string(&canonical_content).unwrap_or_default());
+        hasher.update(node.source.as_bytes());
+        hasher.update(node.confidence.to_bits().to_be_bytes());
+        hasher.update([node.tombstoned as u8]);
+        hasher.finalize().to_vec()
+    }
+
+    /// Merges `remote` into `local` per-field: scalar fields
+    /// (`content`/`confidence`/`source`/`tombstoned`) follow last-writer-wins
+    /// on `updated_at_wall`, falling back to `scalar_fingerprint` when the
+    /// timestamps tie so two replicas converge on the same winner regardless
+    /// of merge order; `connections` is an add-only set union, and
+    /// `access_count` takes the max. Commutative, associative, and
+    /// idempotent, so it's safe to apply in any order or more than once.
+    fn merge_node(mut local: KnowledgeNode, remote: KnowledgeNode) -> KnowledgeNode {
+        let remote_wins = match remote.updated_at_wall.cmp(&local.updated_at_wall) {
+            Ordering::Greater => true,
+            Ordering::Less => false,
+            Ordering::Equal => Self::scalar_fingerprint(&remote) > Self::scalar_fingerprint(&local),
+        };
+
+        if remote_wins {
+            local.content = remote.content;
+            local.confidence = remote.confidence;
+            local.source = remote.source;
+            local.tombstoned = remote.tombstoned;
+            local.updated_at_wall = remote.updated_at_wall;
+        }
+
+        for connection in remote.connections {
+            if !local.connections.contains(&connection) {
+                local.connections.push(connection);
+            }
+        }
+
+        local.access_count = local.access_count.max(remote.access_count);
+        local
+    }
+
+    /// Snapshots the full knowledge graph (including tombstones) for
+    /// exchange with another EVA instance.
+    pub async fn export_delta(&self) -> Vec<KnowledgeNode> {
+        self.knowledge_graph.values().cloned().collect()
+    }
+
+    /// Reconciles `delta` (as produced by another instance's
+    /// `export_delta`) into the local graph via CRDT merge, checkpointing
+    /// every touched node.
+    pub async fn merge_delta(&mut self, delta: Vec<KnowledgeNode>) {
+        let mut touched = Vec::with_capacity(delta.len());
+
+        for remote in delta {
+            let merged = match self.knowledge_graph.remove(&remote.id) {
+                Some(local) => Self::merge_node(local, remote),
+                None => remote,
+            };
+            touched.push(merged.clone());
+            self.knowledge_graph.insert(merged.id.clone(), merged);
+        }
+
+        for node in &touched {
+            self.persist_node(node).await;
+        }
+
+        if !touched.is_empty() {
+            debug!("🔀 Merged {} nodes from a peer delta", touched.len());
+        }
+    }
+
     pub async fn process_perception_data(&mut self, data_type: &str, 
                                        data: HashMap<String, serde_json::Value>) -> Result<(), Box<dyn std::error::Error>> {
         let node_id = format!("{}_{}", data_type, chrono::Utc::now().timestamp_millis());
@@ -145,6 +294,7 @@ impl RustKnowledgeFusion {
 
         self.add_knowledge_node(node_id, content, confidence, format!("rust_{}_sensor", data_type)).await;
         self.fusion_operations += 1;
+        self.metrics.inc_fusion_operations();
 
         // Generate connections after adding new knowledge
         self.generate_knowledge_connections().await;
@@ -153,7 +303,10 @@ impl RustKnowledgeFusion {
     }
 
     async fn generate_knowledge_connections(&mut self) {
-        let node_ids: Vec<String> = self.knowledge_graph.keys().cloned().collect();
+        let node_ids: Vec<String> = self.knowledge_graph.values()
+            .filter(|node| !node.tombstoned)
+            .map(|node| node.id.clone())
+            .collect();
         let mut new_connections = 0;
 
         for i in 0..node_ids.len() {
@@ -224,7 +377,7 @@ impl RustKnowledgeFusion {
 
         // Pattern 1: Most frequent content types
         let mut type_counts = HashMap::new();
-        for node in self.knowledge_graph.values() {
+        for node in self.knowledge_graph.values().filter(|node| !node.tombstoned) {
             if let Some(content_type) = node.content.get("type").and_then(|v| v.as_str()) {
                 *type_counts.entry(content_type.to_string()).or_insert(0) += 1;
             }
@@ -244,7 +397,7 @@ impl RustKnowledgeFusion {
 
         // Pattern 2: High-confidence knowledge clusters
         let high_confidence_nodes: Vec<_> = self.knowledge_graph.values()
-            .filter(|node| node.confidence > 0.9)
+            .filter(|node| !node.tombstoned && node.confidence > 0.9)
             .collect();
 
         if high_confidence_nodes.len() > 3 {
@@ -260,7 +413,18 @@ impl RustKnowledgeFusion {
         }
 
         self.pattern_discoveries += patterns.len() as u64;
-        
+        self.metrics.inc_pattern_discoveries(patterns.len() as u64);
+
+        let live_nodes: Vec<_> = self.knowledge_graph.values().filter(|node| !node.tombstoned).collect();
+        self.metrics.set_knowledge_total_nodes(live_nodes.len());
+
+        let average_confidence = if !live_nodes.is_empty() {
+            live_nodes.iter().map(|node| node.confidence).sum::<f64>() / live_nodes.len() as f64
+        } else {
+            0.0
+        };
+        self.metrics.set_knowledge_average_confidence(average_confidence);
+
         if !patterns.is_empty() {
             info!("🔍 Discovered {} patterns in knowledge graph", patterns.len());
         }
@@ -269,20 +433,20 @@ impl RustKnowledgeFusion {
     }
 
     pub async fn get_status(&self) -> KnowledgeStatus {
-        let total_connections: usize = self.knowledge_graph.values()
+        let live_nodes: Vec<_> = self.knowledge_graph.values().filter(|node| !node.tombstoned).collect();
+
+        let total_connections: usize = live_nodes.iter()
             .map(|node| node.connections.len())
             .sum();
 
-        let average_confidence = if !self.knowledge_graph.is_empty() {
-            self.knowledge_graph.values()
-                .map(|node| node.confidence)
-                .sum::<f64>() / self.knowledge_graph.len() as f64
+        let average_confidence = if !live_nodes.is_empty() {
+            live_nodes.iter().map(|node| node.confidence).sum::<f64>() / live_nodes.len() as f64
         } else {
             0.0
         };
 
         KnowledgeStatus {
-            total_nodes: self.knowledge_graph.len(),
+            total_nodes: live_nodes.len(),
             total_connections,
             fusion_operations: self.fusion_operations,
             average_confidence,
@@ -292,12 +456,15 @@ impl RustKnowledgeFusion {
 
     pub async fn shutdown(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         info!("🧠 Shutting down Rust Knowledge Fusion Engine...");
-        
-        info!("💾 Saving {} knowledge nodes to persistent storage...", self.knowledge_graph.len());
-        // In real implementation: serialize and save knowledge graph
-        
+
+        if self.store.is_some() {
+            info!("💾 {} knowledge nodes already checkpointed to durable storage", self.knowledge_graph.len());
+        } else {
+            info!("💾 No knowledge store configured; {} in-memory knowledge nodes are not saved", self.knowledge_graph.len());
+        }
+
         self.knowledge_graph.clear();
-        
+
         info!("✅ Rust Knowledge Fusion Engine shutdown complete");
         Ok(())
     }
@@ -0,0 +1,74 @@
+/*!
+ * Knowledge Store
+ * SQLite-backed durability for the knowledge graph, with one row per
+ * node so an instance can reload its graph after a restart. Peer delta
+ * exchange (`export_delta` / `merge_delta`) works on the in-memory graph
+ * and does not read from this store.
+ */
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use rusqlite::{params, Connection};
+
+use crate::knowledge_fusion_engine::KnowledgeNode;
+
+#[async_trait::async_trait]
+pub trait KnowledgeStore: Send + Sync {
+    async fn persist(&self, node: &KnowledgeNode) -> Result<(), Box<dyn std::error::Error>>;
+    async fn load_all(&self) -> Result<Vec<KnowledgeNode>, Box<dyn std::error::Error>>;
+}
+
+/// Default `KnowledgeStore` backed by a single SQLite file, one row per
+/// node keyed by id. Good enough for a single-node deployment; swap in a
+/// different `KnowledgeStore` impl (LMDB, a shared database) for anything
+/// heavier.
+pub struct SqliteKnowledgeStore {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteKnowledgeStore {
+    pub fn new(path: impl Into<PathBuf>) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let conn = Connection::open(path)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS knowledge_nodes (
+                id TEXT PRIMARY KEY,
+                payload TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+}
+
+#[async_trait::async_trait]
+impl KnowledgeStore for SqliteKnowledgeStore {
+    async fn persist(&self, node: &KnowledgeNode) -> Result<(), Box<dyn std::error::Error>> {
+        let payload = serde_json::to_string(node)?;
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO knowledge_nodes (id, payload) VALUES (?1, ?2)
+             ON CONFLICT(id) DO UPDATE SET payload = excluded.payload",
+            params![node.id, payload],
+        )?;
+        Ok(())
+    }
+
+    async fn load_all(&self) -> Result<Vec<KnowledgeNode>, Box<dyn std::error::Error>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT payload FROM knowledge_nodes")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+
+        let mut nodes = Vec::new();
+        for row in rows {
+            nodes.push(serde_json::from_str(&row?)?);
+        }
+        Ok(nodes)
+    }
+}
@@ -3,12 +3,20 @@
  * Safety-critical lease management with strict controls
  */
 
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::{mpsc, RwLock};
 use tokio::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use tracing::{info, warn, debug};
 
+use crate::lease_store::{LeaseStore, PersistedLease};
+use crate::metrics::Metrics;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LeaseType {
     Computation,
@@ -25,10 +33,25 @@ pub struct Lease {
     pub lease_type: LeaseType,
     pub granted_at: Instant,
     pub expires_at: Instant,
+    /// Wall-clock mirror of `expires_at`. `Instant` is monotonic and not
+    /// meaningful across a restart, so this is what actually gets
+    /// checkpointed to the `LeaseStore`.
+    pub expires_at_wall: SystemTime,
     pub permissions: HashMap<String, serde_json::Value>,
     pub active: bool,
 }
 
+impl Lease {
+    fn to_persisted(&self) -> PersistedLease {
+        PersistedLease {
+            id: self.id.clone(),
+            lease_type: self.lease_type.clone(),
+            permissions: self.permissions.clone(),
+            expires_at_wall: self.expires_at_wall,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LeaseStatus {
     pub active_leases: usize,
@@ -43,6 +66,18 @@ pub struct RustLeaseManager {
     max_concurrent: usize,
     default_duration: Duration,
     total_granted: usize,
+    // Expiry min-heap keyed by (expires_at, lease_id) so cleanup only pays for
+    // leases that actually expired instead of scanning every active lease.
+    expiry_heap: BinaryHeap<Reverse<(Instant, String)>>,
+    // Sender side of the revoke-drain channel used by `LeaseGuard::drop`, since
+    // `Drop` can't be async. Set via `set_revoke_sender` with the sender
+    // that `spawn_revoke_drain_task` returns.
+    revoke_tx: Option<mpsc::UnboundedSender<String>>,
+    store: Option<Box<dyn LeaseStore>>,
+    // Cap on `revoke_all_leases_throttled`'s revocation rate, mirroring
+    // etcd's configurable `leaseRevokeRate`.
+    max_revokes_per_sec: usize,
+    metrics: Metrics,
 }
 
 impl RustLeaseManager {
@@ -53,19 +88,123 @@ impl RustLeaseManager {
             max_concurrent,
             default_duration,
             total_granted: 0,
+            expiry_heap: BinaryHeap::new(),
+            revoke_tx: None,
+            store: None,
+            max_revokes_per_sec: 50,
+            metrics: Metrics::new(),
+        }
+    }
+
+    pub fn set_store(&mut self, store: Box<dyn LeaseStore>) {
+        self.store = Some(store);
+    }
+
+    pub fn set_metrics(&mut self, metrics: Metrics) {
+        self.metrics = metrics;
+    }
+
+    /// Spawns the background task that drains `LeaseGuard` drop-revocations
+    /// and applies them against `manager`, returning the sender half that
+    /// guards use to enqueue their lease id on drop.
+    pub fn spawn_revoke_drain_task(manager: Arc<RwLock<Self>>) -> mpsc::UnboundedSender<String> {
+        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+
+        tokio::spawn(async move {
+            while let Some(lease_id) = rx.recv().await {
+                manager.write().await.revoke_lease(&lease_id).await;
+            }
+        });
+
+        tx
+    }
+
+    pub fn set_revoke_sender(&mut self, revoke_tx: mpsc::UnboundedSender<String>) {
+        self.revoke_tx = Some(revoke_tx);
+    }
+
+    /// Periodically re-persists every active lease's current wall-clock
+    /// expiry, so a renewal that happens between checkpoints still leaves
+    /// the store consistent with in-memory state.
+    pub fn spawn_checkpoint_task(manager: Arc<RwLock<Self>>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                manager.read().await.checkpoint_all().await;
+            }
+        })
+    }
+
+    async fn checkpoint_all(&self) {
+        let Some(store) = &self.store else {
+            return;
+        };
+
+        for lease in self.active_leases.values() {
+            if let Err(e) = store.persist(&lease.to_persisted()).await {
+                warn!("⚠️ Failed to checkpoint lease {}: {}", lease.id, e);
+            }
         }
     }
 
     pub async fn initialize(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         info!("🔐 Initializing Rust Lease Manager...");
-        
+
         // Clear any existing leases (safety measure)
         self.active_leases.clear();
-        
+        self.expiry_heap.clear();
+
+        if let Some(store) = &self.store {
+            match store.load_all().await {
+                Ok(persisted) => self.recover_from_store(persisted),
+                Err(e) => warn!("⚠️ Failed to load persisted leases: {}", e),
+            }
+        }
+
         info!("✅ Rust Lease Manager initialized");
         Ok(())
     }
 
+    /// Discards leases already past their wall-clock expiry and
+    /// re-registers the rest with a fresh `Instant` deadline computed from
+    /// their remaining wall-clock TTL.
+    fn recover_from_store(&mut self, persisted: Vec<PersistedLease>) {
+        let wall_now = SystemTime::now();
+        let mut recovered = 0;
+
+        for p in persisted {
+            let remaining = match p.expires_at_wall.duration_since(wall_now) {
+                Ok(remaining) => remaining,
+                Err(_) => {
+                    debug!("⏰ Dropping recovered lease {} already past its wall-clock expiry", p.id);
+                    continue;
+                }
+            };
+
+            let now = Instant::now();
+            let lease = Lease {
+                id: p.id.clone(),
+                lease_type: p.lease_type,
+                granted_at: now,
+                expires_at: now + remaining,
+                expires_at_wall: p.expires_at_wall,
+                permissions: p.permissions,
+                active: true,
+            };
+
+            self.expiry_heap.push(Reverse((lease.expires_at, lease.id.clone())));
+            self.active_leases.insert(lease.id.clone(), lease);
+            recovered += 1;
+        }
+
+        if recovered > 0 {
+            info!("♻️ Recovered {} leases from durable store", recovered);
+        }
+
+        self.metrics.set_active_leases(self.active_leases.len());
+    }
+
     pub async fn request_lease(
         &mut self,
         lease_type: &str,
@@ -99,23 +238,94 @@ impl RustLeaseManager {
             lease_type: lease_type_enum,
             granted_at: now,
             expires_at: now + lease_duration,
+            expires_at_wall: SystemTime::now() + lease_duration,
             permissions: HashMap::new(),
             active: true,
         };
 
+        if let Some(store) = &self.store {
+            if let Err(e) = store.persist(&lease.to_persisted()).await {
+                warn!("⚠️ Failed to checkpoint lease {}: {}", lease_id, e);
+            }
+        }
+
+        self.expiry_heap.push(Reverse((lease.expires_at, lease_id.clone())));
         self.active_leases.insert(lease_id.clone(), lease);
         self.total_granted += 1;
+        self.metrics.set_active_leases(self.active_leases.len());
 
-        info!("✅ Granted lease {} for {} ({:?})", 
+        info!("✅ Granted lease {} for {} ({:?})",
               lease_id, lease_type, lease_duration);
 
         Some(lease_id)
     }
 
+    /// Like `request_lease`, but returns a `LeaseGuard` that revokes the
+    /// lease automatically when dropped, so a caller can never forget to
+    /// release a slot.
+    pub async fn request_lease_guarded(
+        &mut self,
+        lease_type: &str,
+        duration: Option<Duration>,
+    ) -> Option<LeaseGuard> {
+        let revoke_tx = self.revoke_tx.clone().unwrap_or_else(|| {
+            warn!("⚠️ No revoke-drain task registered; guard revocations on drop will be dropped");
+            mpsc::unbounded_channel().0
+        });
+
+        let lease_id = self.request_lease(lease_type, duration).await?;
+        let metadata = self.active_leases.get(&lease_id)?.clone();
+
+        Some(LeaseGuard {
+            lease_id,
+            metadata,
+            revoke_tx,
+            released: false,
+        })
+    }
+
+    /// Keepalive: bump a live lease's expiry and push a fresh heap entry,
+    /// leaving the stale entry to be discarded lazily when it's popped.
+    pub async fn renew_lease(&mut self, lease_id: &str, ttl: Option<Duration>) -> bool {
+        let renewal_ttl = ttl.unwrap_or(self.default_duration);
+        let persisted = match self.active_leases.get_mut(lease_id) {
+            Some(lease) if lease.active => {
+                let new_expiry = Instant::now() + renewal_ttl;
+                lease.expires_at = new_expiry;
+                lease.expires_at_wall = SystemTime::now() + renewal_ttl;
+                (new_expiry, lease.to_persisted())
+            }
+            _ => {
+                warn!("❌ Cannot renew unknown or inactive lease {}", lease_id);
+                return false;
+            }
+        };
+
+        let (renewed_expiry, persisted) = persisted;
+
+        if let Some(store) = &self.store {
+            if let Err(e) = store.persist(&persisted).await {
+                warn!("⚠️ Failed to checkpoint renewal of lease {}: {}", lease_id, e);
+            }
+        }
+
+        self.expiry_heap.push(Reverse((renewed_expiry, lease_id.to_string())));
+        debug!("♻️ Renewed lease {} until {:?}", lease_id, renewed_expiry);
+        true
+    }
+
     pub async fn revoke_lease(&mut self, lease_id: &str) -> bool {
         if let Some(mut lease) = self.active_leases.remove(lease_id) {
             lease.active = false;
+
+            if let Some(store) = &self.store {
+                if let Err(e) = store.remove(lease_id).await {
+                    warn!("⚠️ Failed to remove checkpointed lease {}: {}", lease_id, e);
+                }
+            }
+
             self.lease_history.push(lease);
+            self.metrics.set_active_leases(self.active_leases.len());
             info!("🔒 Revoked lease {}", lease_id);
             true
         } else {
@@ -125,25 +335,81 @@ impl RustLeaseManager {
 
     pub async fn revoke_all_leases(&mut self) {
         let lease_ids: Vec<String> = self.active_leases.keys().cloned().collect();
-        
+
         for lease_id in lease_ids {
             self.revoke_lease(&lease_id).await;
         }
-        
+
         warn!("🚨 All leases revoked (emergency procedure)");
     }
 
+    pub fn set_max_revokes_per_sec(&mut self, max_revokes_per_sec: usize) {
+        self.max_revokes_per_sec = max_revokes_per_sec;
+    }
+
+    /// Like `revoke_all_leases`, but drains the active set in time-sliced
+    /// batches of at most `max_revokes_per_sec` so a large holder base
+    /// doesn't see every revocation side-effect fire at once.
+    ///
+    /// Takes `Arc<RwLock<Self>>` rather than `&mut self` and re-acquires the
+    /// write lock per batch instead of holding it across the whole
+    /// multi-second drain: a lockdown is exactly when cleanup, renewals, and
+    /// new lease requests need the manager most, so the drain must not
+    /// monopolize it for the run's entire duration.
+    pub async fn revoke_all_leases_throttled(manager: Arc<RwLock<Self>>) {
+        let (lease_ids, batch_size) = {
+            let this = manager.read().await;
+            (
+                this.active_leases.keys().cloned().collect::<Vec<String>>(),
+                this.max_revokes_per_sec.max(1),
+            )
+        };
+
+        if lease_ids.is_empty() {
+            return;
+        }
+
+        let mut ticker = tokio::time::interval(Duration::from_secs(1));
+
+        for batch in lease_ids.chunks(batch_size) {
+            // `interval`'s first tick fires immediately, so the first batch
+            // goes out right away and subsequent batches are spaced ~1s apart.
+            ticker.tick().await;
+
+            let mut this = manager.write().await;
+            for lease_id in batch {
+                this.revoke_lease(lease_id).await;
+            }
+        }
+
+        warn!(
+            "🚨 All {} leases revoked via throttled emergency procedure ({}/sec)",
+            lease_ids.len(), batch_size
+        );
+    }
+
     pub async fn cleanup_expired_leases(&mut self) {
         let now = Instant::now();
-        let mut expired_leases = Vec::new();
 
-        for (lease_id, lease) in &self.active_leases {
-            if now >= lease.expires_at {
-                expired_leases.push(lease_id.clone());
+        while let Some(Reverse((expires_at, lease_id))) = self.expiry_heap.peek() {
+            if *expires_at > now {
+                break;
+            }
+
+            let (expires_at, lease_id) = self.expiry_heap.pop().unwrap().0;
+
+            // The lease may have been renewed (pushing a fresher heap entry)
+            // or already revoked since this entry was queued; only act on
+            // entries that still match the lease's current expiry.
+            let still_current = self.active_leases
+                .get(&lease_id)
+                .map(|lease| lease.expires_at == expires_at)
+                .unwrap_or(false);
+
+            if !still_current {
+                continue;
             }
-        }
 
-        for lease_id in expired_leases {
             self.revoke_lease(&lease_id).await;
             debug!("⏰ Lease {} expired and removed", lease_id);
         }
@@ -186,4 +452,48 @@ impl RustLeaseManager {
         info!("✅ Rust Lease Manager shutdown complete");
         Ok(())
     }
+}
+
+/// RAII handle for a granted lease. Revokes the lease automatically when
+/// dropped (via the manager's revoke-drain channel, since `Drop` can't be
+/// async), so a forgotten `revoke_lease` call can no longer leak a slot.
+pub struct LeaseGuard {
+    lease_id: String,
+    metadata: Lease,
+    revoke_tx: mpsc::UnboundedSender<String>,
+    released: bool,
+}
+
+impl LeaseGuard {
+    pub fn lease_id(&self) -> &str {
+        &self.lease_id
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.metadata.active && Instant::now() < self.metadata.expires_at
+    }
+
+    /// Revoke the lease eagerly instead of waiting for drop.
+    pub fn release(mut self) {
+        if !self.released {
+            let _ = self.revoke_tx.send(self.lease_id.clone());
+            self.released = true;
+        }
+    }
+}
+
+impl Deref for LeaseGuard {
+    type Target = Lease;
+
+    fn deref(&self) -> &Lease {
+        &self.metadata
+    }
+}
+
+impl Drop for LeaseGuard {
+    fn drop(&mut self) {
+        if !self.released {
+            let _ = self.revoke_tx.send(self.lease_id.clone());
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,85 @@
+/*!
+ * Lease Store
+ * Durable checkpointing so lease grants survive a process restart
+ */
+
+use std::path::PathBuf;
+use std::time::SystemTime;
+use serde::{Deserialize, Serialize};
+
+use crate::lease_manager::LeaseType;
+
+/// On-disk representation of a lease. Unlike `Lease`, which tracks expiry
+/// as a `tokio::time::Instant` (monotonic, not serializable across a
+/// restart), this stores an absolute wall-clock expiry so it can be
+/// reloaded and converted back into an `Instant` deadline later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedLease {
+    pub id: String,
+    pub lease_type: LeaseType,
+    pub permissions: std::collections::HashMap<String, serde_json::Value>,
+    pub expires_at_wall: SystemTime,
+}
+
+#[async_trait::async_trait]
+pub trait LeaseStore: Send + Sync {
+    async fn persist(&self, lease: &PersistedLease) -> Result<(), Box<dyn std::error::Error>>;
+    async fn remove(&self, lease_id: &str) -> Result<(), Box<dyn std::error::Error>>;
+    async fn load_all(&self) -> Result<Vec<PersistedLease>, Box<dyn std::error::Error>>;
+}
+
+/// Default `LeaseStore` backed by a single JSON file holding the full set
+/// of currently-persisted leases. Adequate for a single-node deployment;
+/// swap in a different `LeaseStore` impl for anything heavier.
+pub struct FileLeaseStore {
+    path: PathBuf,
+}
+
+impl FileLeaseStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    fn read_all_blocking(&self) -> Result<Vec<PersistedLease>, Box<dyn std::error::Error>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let data = std::fs::read_to_string(&self.path)?;
+        if data.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Ok(serde_json::from_str(&data)?)
+    }
+
+    fn write_all_blocking(&self, leases: &[PersistedLease]) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let data = serde_json::to_string_pretty(leases)?;
+        std::fs::write(&self.path, data)?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl LeaseStore for FileLeaseStore {
+    async fn persist(&self, lease: &PersistedLease) -> Result<(), Box<dyn std::error::Error>> {
+        let mut leases = self.read_all_blocking()?;
+        leases.retain(|l| l.id != lease.id);
+        leases.push(lease.clone());
+        self.write_all_blocking(&leases)
+    }
+
+    async fn remove(&self, lease_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut leases = self.read_all_blocking()?;
+        leases.retain(|l| l.id != lease_id);
+        self.write_all_blocking(&leases)
+    }
+
+    async fn load_all(&self) -> Result<Vec<PersistedLease>, Box<dyn std::error::Error>> {
+        self.read_all_blocking()
+    }
+}
@@ -4,20 +4,33 @@
  */
 
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio::time::{Duration, Instant};
 use tracing::{info, warn, error};
 
+mod access_control;
+mod admin_api;
 mod lease_manager;
+mod lease_store;
+mod metrics;
 mod policy_engine;
 mod knowledge_fusion_engine;
+mod knowledge_store;
 mod kill_switch;
+mod quorum_auth;
+mod worker_manager;
 
+use access_control::UserData;
 use lease_manager::RustLeaseManager;
+use lease_store::FileLeaseStore;
+use metrics::Metrics;
 use policy_engine::RustPolicyEngine;
 use knowledge_fusion_engine::RustKnowledgeFusion;
+use knowledge_store::SqliteKnowledgeStore;
 use kill_switch::EmergencyKillSwitch;
+use worker_manager::{BackgroundWorker, WorkerManager, WorkerState};
 
 #[derive(Debug, Clone)]
 pub struct EVAConfig {
@@ -25,6 +38,31 @@ pub struct EVAConfig {
     pub default_lease_duration: Duration,
     pub emergency_timeout: Duration,
     pub safety_check_interval: Duration,
+    pub lease_store_path: String,
+    pub knowledge_store_path: String,
+    pub metrics_addr: SocketAddr,
+    pub admin_api_addr: SocketAddr,
+    pub admin_api_bearer_token: String,
+    /// Path to a file holding `admin_api_bearer_token` instead of baking it
+    /// into source, an env var, or a command line. Resolved by `load()`;
+    /// setting this alongside a non-empty `admin_api_bearer_token` is an
+    /// error rather than a silent override.
+    pub admin_api_bearer_token_file: Option<String>,
+    /// Threshold and total share count for quorum-based kill-switch reset.
+    /// `0` (the default) leaves quorum reset unconfigured, matching today's
+    /// unilateral `reset`.
+    pub quorum_threshold: usize,
+    pub quorum_total_shares: usize,
+    /// Hex-encoded `QuorumAuth` commitment (`QuorumAuth::commitment_hex`)
+    /// restored at `initialize` time instead of generating a fresh secret,
+    /// so a restart doesn't invalidate shares already handed to operators.
+    pub emergency_secret_commitment: String,
+    /// Path to a file holding `emergency_secret_commitment` instead of
+    /// baking it into source, an env var, or a command line. Resolved by
+    /// `load()`; setting this alongside a non-empty
+    /// `emergency_secret_commitment` is an error rather than a silent
+    /// override.
+    pub emergency_secret_commitment_file: Option<String>,
 }
 
 impl Default for EVAConfig {
@@ -34,17 +72,126 @@ impl Default for EVAConfig {
             default_lease_duration: Duration::from_secs(300), // 5 minutes
             emergency_timeout: Duration::from_secs(5),
             safety_check_interval: Duration::from_secs(1),
+            lease_store_path: "data/leases.json".to_string(),
+            knowledge_store_path: "data/knowledge.sqlite3".to_string(),
+            metrics_addr: SocketAddr::from(([127, 0, 0, 1], 9898)),
+            admin_api_addr: SocketAddr::from(([127, 0, 0, 1], 9899)),
+            admin_api_bearer_token: String::new(),
+            admin_api_bearer_token_file: None,
+            quorum_threshold: 0,
+            quorum_total_shares: 0,
+            emergency_secret_commitment: String::new(),
+            emergency_secret_commitment_file: None,
         }
     }
 }
 
+impl EVAConfig {
+    /// Builds the default config, then resolves any `*_file` secret
+    /// fields by reading the referenced path, so the secret itself never
+    /// needs to appear inline, in an env var, or on a command line.
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let mut config = Self::default();
+        config.resolve_secret_files()?;
+        Ok(config)
+    }
+
+    fn resolve_secret_files(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.admin_api_bearer_token = Self::resolve_secret_field(
+            "admin_api_bearer_token",
+            &self.admin_api_bearer_token,
+            self.admin_api_bearer_token_file.take(),
+        )?;
+        self.emergency_secret_commitment = Self::resolve_secret_field(
+            "emergency_secret_commitment",
+            &self.emergency_secret_commitment,
+            self.emergency_secret_commitment_file.take(),
+        )?;
+        Ok(())
+    }
+
+    /// Resolves a single `name` / `name_file` pair: a file reference wins
+    /// when the inline value is empty, an inline value wins when no file
+    /// is set, and having both set is a clear error rather than a silent
+    /// override in either direction.
+    fn resolve_secret_field(
+        name: &str,
+        inline: &str,
+        file: Option<String>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        match (inline.is_empty(), file) {
+            (true, Some(path)) => Ok(std::fs::read_to_string(&path)?.trim().to_string()),
+            (true, None) => Ok(inline.to_string()),
+            (false, None) => Ok(inline.to_string()),
+            (false, Some(_)) => Err(format!(
+                "both `{name}` and `{name}_file` are set - provide the secret only one way"
+            ).into()),
+        }
+    }
+}
+
+/// Polls the kill switch on `safety_check_interval` and flips `running` off
+/// the moment an emergency stop is warranted.
+struct SafetyMonitorWorker {
+    kill_switch: Arc<RwLock<EmergencyKillSwitch>>,
+    running: Arc<RwLock<bool>>,
+    interval: Duration,
+}
+
+#[async_trait::async_trait]
+impl BackgroundWorker for SafetyMonitorWorker {
+    fn name(&self) -> &str {
+        "safety_monitor"
+    }
+
+    async fn step(&mut self) -> Result<WorkerState, String> {
+        if !*self.running.read().await {
+            return Ok(WorkerState::Done);
+        }
+
+        if self.kill_switch.read().await.should_emergency_stop().await {
+            warn!("🚨 Emergency kill switch activated!");
+            *self.running.write().await = false;
+            return Ok(WorkerState::Done);
+        }
+
+        tokio::time::sleep(self.interval).await;
+        Ok(WorkerState::Active)
+    }
+}
+
+/// Sweeps expired leases every ten seconds while the core is running.
+struct LeaseCleanupWorker {
+    lease_manager: Arc<RwLock<RustLeaseManager>>,
+    running: Arc<RwLock<bool>>,
+}
+
+#[async_trait::async_trait]
+impl BackgroundWorker for LeaseCleanupWorker {
+    fn name(&self) -> &str {
+        "lease_cleanup"
+    }
+
+    async fn step(&mut self) -> Result<WorkerState, String> {
+        if !*self.running.read().await {
+            return Ok(WorkerState::Done);
+        }
+
+        self.lease_manager.write().await.cleanup_expired_leases().await;
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        Ok(WorkerState::Active)
+    }
+}
+
 pub struct EVARustCore {
     config: EVAConfig,
     lease_manager: Arc<RwLock<RustLeaseManager>>,
     policy_engine: Arc<RwLock<RustPolicyEngine>>,
     knowledge_fusion: Arc<RwLock<RustKnowledgeFusion>>,
     kill_switch: Arc<RwLock<EmergencyKillSwitch>>,
+    worker_manager: Arc<RwLock<WorkerManager>>,
     running: Arc<RwLock<bool>>,
+    metrics: Metrics,
 }
 
 impl EVARustCore {
@@ -65,19 +212,62 @@ impl EVARustCore {
             policy_engine,
             knowledge_fusion,
             kill_switch,
+            worker_manager: Arc::new(RwLock::new(WorkerManager::new())),
             running: Arc::new(RwLock::new(false)),
+            metrics: Metrics::new(),
         }
     }
 
     pub async fn initialize(&self) -> Result<(), Box<dyn std::error::Error>> {
         info!("🦀 Initializing EVA Rust Core Systems...");
 
+        // Wire up durable lease checkpointing before `initialize` so it can
+        // recover any leases persisted before a restart.
+        self.lease_manager.write().await
+            .set_store(Box::new(FileLeaseStore::new(&self.config.lease_store_path)));
+
+        // Same for the knowledge graph - load before `initialize` so
+        // `initialize_base_knowledge` only runs for a genuinely empty store.
+        match SqliteKnowledgeStore::new(&self.config.knowledge_store_path) {
+            Ok(store) => self.knowledge_fusion.write().await.set_store(Box::new(store)),
+            Err(e) => warn!("⚠️ Failed to open knowledge store {}: {}", self.config.knowledge_store_path, e),
+        }
+
+        // Share one metrics registry across every subsystem that exposes a
+        // Prometheus series, then serve it over HTTP.
+        self.lease_manager.write().await.set_metrics(self.metrics.clone());
+        self.knowledge_fusion.write().await.set_metrics(self.metrics.clone());
+        self.kill_switch.write().await.set_metrics(self.metrics.clone());
+        self.metrics.clone().spawn_http_server(self.config.metrics_addr);
+
         // Initialize all subsystems
         self.policy_engine.write().await.initialize().await?;
         self.lease_manager.write().await.initialize().await?;
         self.knowledge_fusion.write().await.initialize().await?;
         self.kill_switch.write().await.initialize().await?;
 
+        // Restore quorum-based kill-switch reset from a commitment captured
+        // on a prior run, if configured - see `EVAConfig::emergency_secret_commitment`.
+        if self.config.quorum_threshold > 0 && !self.config.emergency_secret_commitment.is_empty() {
+            self.kill_switch.write().await.configure_quorum_from_commitment(
+                self.config.quorum_threshold,
+                self.config.quorum_total_shares,
+                &self.config.emergency_secret_commitment,
+            )?;
+        }
+
+        // Wire up the revoke-drain task so `LeaseGuard`s can revoke
+        // themselves on drop.
+        let revoke_tx = RustLeaseManager::spawn_revoke_drain_task(Arc::clone(&self.lease_manager));
+        self.lease_manager.write().await.set_revoke_sender(revoke_tx);
+
+        // Periodically re-checkpoint active leases so renewals survive a
+        // restart even between explicit persist calls.
+        RustLeaseManager::spawn_checkpoint_task(
+            Arc::clone(&self.lease_manager),
+            Duration::from_secs(30),
+        );
+
         info!("✅ EVA Rust Core Systems initialized successfully");
         Ok(())
     }
@@ -87,41 +277,48 @@ impl EVARustCore {
         
         *self.running.write().await = true;
 
-        // Start safety monitoring
-        let kill_switch = Arc::clone(&self.kill_switch);
-        let running = Arc::clone(&self.running);
-        let safety_interval = self.config.safety_check_interval;
-        
-        tokio::spawn(async move {
-            while *running.read().await {
-                if kill_switch.read().await.should_emergency_stop().await {
-                    warn!("🚨 Emergency kill switch activated!");
-                    *running.write().await = false;
-                    break;
-                }
-                tokio::time::sleep(safety_interval).await;
-            }
+        let mut worker_manager = self.worker_manager.write().await;
+
+        worker_manager.spawn(SafetyMonitorWorker {
+            kill_switch: Arc::clone(&self.kill_switch),
+            running: Arc::clone(&self.running),
+            interval: self.config.safety_check_interval,
         });
 
-        // Start lease monitoring
-        let lease_manager = Arc::clone(&self.lease_manager);
-        let running_clone = Arc::clone(&self.running);
-        
-        tokio::spawn(async move {
-            while *running_clone.read().await {
-                lease_manager.write().await.cleanup_expired_leases().await;
-                tokio::time::sleep(Duration::from_secs(10)).await;
-            }
+        worker_manager.spawn(LeaseCleanupWorker {
+            lease_manager: Arc::clone(&self.lease_manager),
+            running: Arc::clone(&self.running),
         });
 
         info!("✅ EVA Rust Core started successfully");
         Ok(())
     }
 
-    pub async fn request_lease(&self, lease_type: &str, duration: Option<Duration>) -> Option<String> {
-        let policy_check = self.policy_engine.read().await
-            .can_grant_lease(lease_type).await;
-            
+    /// Spawns the admin HTTP API. Takes `Arc<Self>` (rather than living on
+    /// `start`, which only needs `&self`) since every request handler needs
+    /// to hold its own cloned handle to the core across the server's
+    /// lifetime.
+    pub async fn start_admin_api(self: &Arc<Self>) {
+        if self.config.admin_api_bearer_token.is_empty() {
+            warn!("⚠️ Admin API bearer token is empty; destructive endpoints are effectively unguarded");
+        }
+
+        admin_api::spawn(
+            Arc::clone(self),
+            self.config.admin_api_bearer_token.clone(),
+            self.config.admin_api_addr,
+        );
+    }
+
+    pub async fn request_lease(
+        &self,
+        lease_type: &str,
+        duration: Option<Duration>,
+        user: &UserData,
+    ) -> Option<String> {
+        let policy_check = self.policy_engine.write().await
+            .can_grant_lease(lease_type, &HashMap::new(), user).await;
+
         if !policy_check {
             warn!("❌ Lease request denied by policy: {}", lease_type);
             return None;
@@ -131,6 +328,10 @@ impl EVARustCore {
             .request_lease(lease_type, duration).await
     }
 
+    pub async fn revoke_all_leases(&self) {
+        self.lease_manager.write().await.revoke_all_leases().await;
+    }
+
     pub async fn emergency_stop(&self) -> Result<(), Box<dyn std::error::Error>> {
         warn!("🚨 Emergency stop initiated!");
         
@@ -162,9 +363,17 @@ impl EVARustCore {
         status.insert("knowledge".to_string(),
             serde_json::to_value(knowledge_status).unwrap_or_default());
 
+        let workers = self.worker_manager.read().await.list_workers().await;
+        status.insert("workers".to_string(),
+            serde_json::to_value(workers).unwrap_or_default());
+
         status
     }
 
+    pub async fn list_workers(&self) -> Vec<worker_manager::WorkerInfo> {
+        self.worker_manager.read().await.list_workers().await
+    }
+
     pub async fn shutdown(&self) -> Result<(), Box<dyn std::error::Error>> {
         info!("🛑 Shutting down EVA Rust Core...");
         
@@ -181,6 +390,36 @@ impl EVARustCore {
     }
 }
 
+/// Deterministic timing test for `SafetyMonitorWorker`, the loop
+/// `EVARustCore::start` spawns to poll the kill switch. Uses
+/// `tokio::time`'s virtual clock (see the test module in `kill_switch.rs`
+/// for why that stands in for a hand-rolled sim harness) so the
+/// "stops within one `safety_check_interval`" requirement is asserted on
+/// a single `step()` call instead of a real sleep.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn safety_monitor_worker_stops_one_interval_after_activation() {
+        let kill_switch = Arc::new(RwLock::new(EmergencyKillSwitch::new(Duration::from_secs(5))));
+        kill_switch.write().await.initialize().await.unwrap();
+        kill_switch.write().await.activate().await.unwrap();
+
+        let running = Arc::new(RwLock::new(true));
+        let mut worker = SafetyMonitorWorker {
+            kill_switch: Arc::clone(&kill_switch),
+            running: Arc::clone(&running),
+            interval: Duration::from_millis(100),
+        };
+
+        let state = worker.step().await.unwrap();
+
+        assert_eq!(state, WorkerState::Done);
+        assert!(!*running.read().await);
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize tracing
@@ -188,13 +427,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     info!("🌌 Starting EVA-OFO-001 Rust Core...");
     
-    let config = EVAConfig::default();
-    let eva_core = EVARustCore::new(config);
-    
+    let config = EVAConfig::load()?;
+    let eva_core = Arc::new(EVARustCore::new(config));
+
     // Initialize and start
     eva_core.initialize().await?;
     eva_core.start().await?;
-    
+    eva_core.start_admin_api().await;
+
     // Setup graceful shutdown
     tokio::signal::ctrl_c().await?;
     info!("Received shutdown signal");
@@ -0,0 +1,182 @@
+/*!
+ * Metrics
+ * Prometheus-style counters/gauges for every subsystem, served over HTTP
+ */
+
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::Arc;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
+use tracing::{error, info};
+
+use crate::kill_switch::ViolationSeverity;
+
+#[derive(Default, Debug)]
+struct Counters {
+    fusion_operations: AtomicU64,
+    pattern_discoveries: AtomicU64,
+    safety_violations_low: AtomicU64,
+    safety_violations_medium: AtomicU64,
+    safety_violations_high: AtomicU64,
+    safety_violations_critical: AtomicU64,
+}
+
+#[derive(Default, Debug)]
+struct Gauges {
+    knowledge_total_nodes: AtomicI64,
+    // Confidence scaled by 1000 since there's no portable AtomicF64.
+    knowledge_average_confidence_milli: AtomicI64,
+    active_leases: AtomicI64,
+    kill_switch_activated: AtomicI64,
+}
+
+/// Shared metrics registry, cheap to clone (everything behind `Arc`) so
+/// every subsystem can hold its own handle and update it inline.
+#[derive(Clone, Default, Debug)]
+pub struct Metrics {
+    counters: Arc<Counters>,
+    gauges: Arc<Gauges>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn inc_fusion_operations(&self) {
+        self.counters.fusion_operations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn inc_pattern_discoveries(&self, by: u64) {
+        self.counters.pattern_discoveries.fetch_add(by, Ordering::Relaxed);
+    }
+
+    pub fn record_safety_violation(&self, severity: &ViolationSeverity) {
+        let counter = match severity {
+            ViolationSeverity::Low => &self.counters.safety_violations_low,
+            ViolationSeverity::Medium => &self.counters.safety_violations_medium,
+            ViolationSeverity::High => &self.counters.safety_violations_high,
+            ViolationSeverity::Critical => &self.counters.safety_violations_critical,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn set_knowledge_total_nodes(&self, value: usize) {
+        self.gauges.knowledge_total_nodes.store(value as i64, Ordering::Relaxed);
+    }
+
+    pub fn set_knowledge_average_confidence(&self, value: f64) {
+        self.gauges
+            .knowledge_average_confidence_milli
+            .store((value * 1000.0).round() as i64, Ordering::Relaxed);
+    }
+
+    pub fn set_active_leases(&self, value: usize) {
+        self.gauges.active_leases.store(value as i64, Ordering::Relaxed);
+    }
+
+    pub fn set_kill_switch_activated(&self, activated: bool) {
+        self.gauges.kill_switch_activated.store(activated as i64, Ordering::Relaxed);
+    }
+
+    /// Renders every registered series in Prometheus text exposition
+    /// format, suitable for a scrape of `GET /metrics`.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP fusion_operations_total Knowledge fusion operations processed\n");
+        out.push_str("# TYPE fusion_operations_total counter\n");
+        out.push_str(&format!(
+            "fusion_operations_total {}\n",
+            self.counters.fusion_operations.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# HELP pattern_discoveries_total Patterns discovered in the knowledge graph\n");
+        out.push_str("# TYPE pattern_discoveries_total counter\n");
+        out.push_str(&format!(
+            "pattern_discoveries_total {}\n",
+            self.counters.pattern_discoveries.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# HELP knowledge_total_nodes Nodes currently in the knowledge graph\n");
+        out.push_str("# TYPE knowledge_total_nodes gauge\n");
+        out.push_str(&format!(
+            "knowledge_total_nodes {}\n",
+            self.gauges.knowledge_total_nodes.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# HELP knowledge_average_confidence Average confidence across knowledge nodes\n");
+        out.push_str("# TYPE knowledge_average_confidence gauge\n");
+        out.push_str(&format!(
+            "knowledge_average_confidence {:.3}\n",
+            self.gauges.knowledge_average_confidence_milli.load(Ordering::Relaxed) as f64 / 1000.0
+        ));
+
+        out.push_str("# HELP active_leases Currently active leases\n");
+        out.push_str("# TYPE active_leases gauge\n");
+        out.push_str(&format!(
+            "active_leases {}\n",
+            self.gauges.active_leases.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# HELP safety_violations_total Safety violations recorded, by severity\n");
+        out.push_str("# TYPE safety_violations_total counter\n");
+        out.push_str(&format!(
+            "safety_violations_total{{severity=\"low\"}} {}\n",
+            self.counters.safety_violations_low.load(Ordering::Relaxed)
+        ));
+        out.push_str(&format!(
+            "safety_violations_total{{severity=\"medium\"}} {}\n",
+            self.counters.safety_violations_medium.load(Ordering::Relaxed)
+        ));
+        out.push_str(&format!(
+            "safety_violations_total{{severity=\"high\"}} {}\n",
+            self.counters.safety_violations_high.load(Ordering::Relaxed)
+        ));
+        out.push_str(&format!(
+            "safety_violations_total{{severity=\"critical\"}} {}\n",
+            self.counters.safety_violations_critical.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# HELP kill_switch_activated Whether the emergency kill switch is currently activated\n");
+        out.push_str("# TYPE kill_switch_activated gauge\n");
+        out.push_str(&format!(
+            "kill_switch_activated {}\n",
+            self.gauges.kill_switch_activated.load(Ordering::Relaxed)
+        ));
+
+        out
+    }
+
+    /// Serves `render()`'s output on `GET /metrics` at `addr`.
+    pub fn spawn_http_server(self, addr: SocketAddr) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let metrics = self;
+            let make_svc = make_service_fn(move |_conn| {
+                let metrics = metrics.clone();
+                async move {
+                    Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| {
+                        let metrics = metrics.clone();
+                        async move {
+                            let response = if req.uri().path() == "/metrics" {
+                                Response::new(Body::from(metrics.render()))
+                            } else {
+                                let mut not_found = Response::new(Body::from("not found"));
+                                *not_found.status_mut() = hyper::StatusCode::NOT_FOUND;
+                                not_found
+                            };
+                            Ok::<_, hyper::Error>(response)
+                        }
+                    }))
+                }
+            });
+
+            info!("📈 Metrics endpoint listening on http://{}/metrics", addr);
+
+            if let Err(e) = Server::bind(&addr).serve(make_svc).await {
+                error!("metrics server error: {}", e);
+            }
+        })
+    }
+}
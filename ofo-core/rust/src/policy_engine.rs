@@ -4,10 +4,33 @@
  */
 
 use std::collections::HashMap;
+use std::sync::Arc;
+use cel_interpreter::{Context as CelContext, Program, Value as CelValue};
 use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
 use tokio::time::Instant;
 use tracing::{info, warn, error};
 
+use crate::access_control::{AccessControl, Permission, Role, PermRule, UserData};
+use crate::lease_manager::RustLeaseManager;
+
+/// Outcome applied when a policy's condition fails to evaluate (a bug in
+/// the expression, a missing context variable, etc). `Deny` fails closed;
+/// `Allow` fails open for deployments where availability trumps strictness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FailureMode {
+    Deny,
+    Allow,
+}
+
+impl Default for FailureMode {
+    fn default() -> Self {
+        // Safety-critical engine: an evaluation we can't trust should not
+        // silently grant a lease.
+        FailureMode::Deny
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PolicyLevel {
     Critical,
@@ -24,6 +47,10 @@ pub struct Policy {
     pub level: PolicyLevel,
     pub rules: HashMap<String, serde_json::Value>,
     pub active: bool,
+    /// CEL expression evaluated against the lease request context in
+    /// `check_policy_compliance`. `None` means the policy carries no
+    /// evaluable condition (it's informational only).
+    pub condition: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -39,6 +66,11 @@ pub struct RustPolicyEngine {
     violations: Vec<PolicyViolation>,
     enforcement_active: bool,
     last_safety_check: Instant,
+    // Compiled CEL programs for each policy's `condition`, keyed by policy id
+    // so `can_grant_lease` doesn't re-parse an expression on every check.
+    compiled_conditions: HashMap<String, Program>,
+    access_control: AccessControl,
+    failure_mode: FailureMode,
 }
 
 #[derive(Debug, Clone)]
@@ -57,20 +89,68 @@ impl RustPolicyEngine {
             violations: Vec::new(),
             enforcement_active: true,
             last_safety_check: Instant::now(),
+            compiled_conditions: HashMap::new(),
+            access_control: AccessControl::new(),
+            failure_mode: FailureMode::default(),
         }
     }
 
     pub async fn initialize(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         info!("⚖️ Initializing Rust Policy Engine...");
-        
+
         self.load_core_policies().await?;
+        self.compile_conditions()?;
+        self.load_default_roles();
         self.enforcement_active = true;
         self.last_safety_check = Instant::now();
-        
+
         info!("✅ Rust Policy Engine initialized with {} policies", self.policies.len());
         Ok(())
     }
 
+    /// Registers the built-in roles that gate consent-sensitive lease
+    /// types. Operators can extend this via `access_control_mut`.
+    fn load_default_roles(&mut self) {
+        self.access_control.register_role(Role {
+            id: "operator".to_string(),
+            permissions: vec![
+                PermRule { permission: "lease.device_control".to_string() },
+                PermRule { permission: "lease.data_access".to_string() },
+            ],
+            parents: Vec::new(),
+        });
+
+        self.access_control.register_role(Role {
+            id: "guest".to_string(),
+            permissions: Vec::new(),
+            parents: Vec::new(),
+        });
+    }
+
+    pub fn access_control_mut(&mut self) -> &mut AccessControl {
+        &mut self.access_control
+    }
+
+    /// Compiles each policy's `condition` CEL source into a `Program` once,
+    /// so evaluation in `check_policy_compliance` is just an `execute` call.
+    fn compile_conditions(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.compiled_conditions.clear();
+
+        for policy in self.policies.values() {
+            let Some(condition) = &policy.condition else {
+                continue;
+            };
+
+            let program = Program::compile(condition).map_err(|e| {
+                format!("failed to compile condition for policy {}: {:?}", policy.id, e)
+            })?;
+
+            self.compiled_conditions.insert(policy.id.clone(), program);
+        }
+
+        Ok(())
+    }
+
     async fn load_core_policies(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         // Critical safety policy
         let mut safety_rules = HashMap::new();
@@ -89,6 +169,9 @@ impl RustPolicyEngine {
             level: PolicyLevel::Critical,
             rules: safety_rules,
             active: true,
+            condition: Some(
+                "lease_type != \"device_control\" || request.has_consent".to_string(),
+            ),
         };
 
         // Resource limits policy
@@ -104,6 +187,7 @@ impl RustPolicyEngine {
             level: PolicyLevel::High,
             rules: resource_rules,
             active: true,
+            condition: None,
         };
 
         // Learning constraints policy
@@ -119,6 +203,7 @@ impl RustPolicyEngine {
             level: PolicyLevel::High,
             rules: learning_rules,
             active: true,
+            condition: None,
         };
 
         self.policies.insert(safety_policy.id.clone(), safety_policy);
@@ -128,49 +213,119 @@ impl RustPolicyEngine {
         Ok(())
     }
 
-    pub async fn can_grant_lease(&self, lease_type: &str) -> bool {
+    pub async fn can_grant_lease(
+        &mut self,
+        lease_type: &str,
+        requested_permissions: &HashMap<String, serde_json::Value>,
+        user: &UserData,
+    ) -> bool {
         if !self.enforcement_active {
             return true;
         }
 
-        for policy in self.policies.values() {
+        let requires_consent = self.consent_required_lease_types().contains(&lease_type.to_string());
+        let has_consent = !requires_consent
+            || self.access_control.check(user, &Permission::for_lease_type(lease_type));
+
+        if requires_consent && !has_consent {
+            warn!("❌ Lease denied: user {} lacks consent permission for {}", user.id, lease_type);
+            return false;
+        }
+
+        let mut request_context = requested_permissions.clone();
+        request_context.insert("has_consent".to_string(), serde_json::Value::Bool(has_consent));
+
+        let policies: Vec<Policy> = self.policies.values().cloned().collect();
+
+        for policy in &policies {
             if !policy.active {
                 continue;
             }
 
-            if !self.check_policy_compliance(policy, lease_type) {
-                warn!("❌ Lease denied by Rust policy: {}", policy.name);
-                return false;
+            match self.check_policy_compliance(policy, lease_type, &request_context) {
+                Ok(true) => continue,
+                Ok(false) => {
+                    warn!("❌ Lease denied by Rust policy: {}", policy.name);
+                    return false;
+                }
+                Err(e) => {
+                    match self.failure_mode {
+                        FailureMode::Deny => {
+                            error!(
+                                "🚨 Policy {} evaluation failed ({}); denying lease (fail-closed)",
+                                policy.name, e
+                            );
+                            self.report_violation(
+                                policy.id.clone(),
+                                "policy_evaluation_error".to_string(),
+                                PolicyLevel::High,
+                                format!("Policy {} failed to evaluate: {}", policy.name, e),
+                            ).await;
+                            return false;
+                        }
+                        FailureMode::Allow => {
+                            warn!(
+                                "⚠️ Policy {} evaluation failed ({}); granting lease (fail-open)",
+                                policy.name, e
+                            );
+                        }
+                    }
+                }
             }
         }
 
         true
     }
 
-    fn check_policy_compliance(&self, policy: &Policy, lease_type: &str) -> bool {
-        match policy.id.as_str() {
-            "rust_safety_001" => {
-                if lease_type == "device_control" {
-                    // In real implementation, check for user consent
-                    return true; // Simplified for demo
-                }
-            },
-            "rust_resource_001" => {
-                if lease_type == "computation" {
-                    // Check resource constraints
-                    return true; // Simplified for demo
-                }
-            },
-            "rust_learning_001" => {
-                if lease_type == "learning" || lease_type == "experimentation" {
-                    // Verify learning safety constraints
-                    return true; // Simplified for demo
-                }
-            },
-            _ => {}
+    pub fn set_failure_mode(&mut self, mode: FailureMode) {
+        self.failure_mode = mode;
+    }
+
+    /// Lease types the `human_consent_required` rule on the safety policy
+    /// names as requiring a granted `lease.<type>` permission.
+    fn consent_required_lease_types(&self) -> Vec<String> {
+        self.policies
+            .get("rust_safety_001")
+            .and_then(|p| p.rules.get("human_consent_required"))
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Evaluates `policy.condition` (if any) against a CEL context built
+    /// from the lease request. A policy with no condition carries no
+    /// evaluable constraint and is treated as satisfied. Any failure to
+    /// build the context or evaluate the expression is surfaced as `Err`
+    /// so the caller can apply the configured `FailureMode`.
+    fn check_policy_compliance(
+        &self,
+        policy: &Policy,
+        lease_type: &str,
+        requested_permissions: &HashMap<String, serde_json::Value>,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let Some(program) = self.compiled_conditions.get(&policy.id) else {
+            return Ok(true);
+        };
+
+        let mut ctx = CelContext::default();
+
+        ctx.add_variable("lease_type", lease_type.to_string())
+            .map_err(|e| format!("failed to bind lease_type for policy {}: {:?}", policy.id, e))?;
+
+        ctx.add_variable("request", requested_permissions.clone())
+            .map_err(|e| format!("failed to bind request context for policy {}: {:?}", policy.id, e))?;
+
+        match program.execute(&ctx) {
+            Ok(CelValue::Bool(allowed)) => Ok(allowed),
+            Ok(other) => Err(format!(
+                "policy {} condition did not evaluate to a bool: {:?}",
+                policy.id, other
+            ).into()),
+            Err(e) => Err(format!(
+                "policy {} condition evaluation failed: {:?}",
+                policy.id, e
+            ).into()),
         }
-        
-        true
     }
 
     pub async fn report_violation(&mut self, policy_id: String, violation_type: String, 
@@ -202,9 +357,17 @@ impl RustPolicyEngine {
         }
     }
 
-    pub async fn emergency_lockdown(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    /// Disables all non-critical policies and revokes every active lease.
+    /// `Critical` severity revokes immediately; anything lower goes through
+    /// `revoke_all_leases_throttled` so a large holder base doesn't see a
+    /// revocation burst.
+    pub async fn emergency_lockdown(
+        &mut self,
+        severity: PolicyLevel,
+        lease_manager: Arc<RwLock<RustLeaseManager>>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         error!("🚨 EMERGENCY POLICY LOCKDOWN ACTIVATED");
-        
+
         // Disable all non-critical policies temporarily
         for policy in self.policies.values_mut() {
             if !matches!(policy.level, PolicyLevel::Critical) {
@@ -213,7 +376,18 @@ impl RustPolicyEngine {
         }
 
         self.enforcement_active = true;
-        
+
+        match severity {
+            PolicyLevel::Critical => {
+                warn!("🚨 Critical lockdown severity: revoking all leases immediately");
+                lease_manager.write().await.revoke_all_leases().await;
+            }
+            _ => {
+                warn!("⚠️ Lockdown severity {:?}: revoking leases via throttled procedure", severity);
+                RustLeaseManager::revoke_all_leases_throttled(Arc::clone(&lease_manager)).await;
+            }
+        }
+
         info!("🔒 Emergency lockdown complete - only critical policies active");
         Ok(())
     }
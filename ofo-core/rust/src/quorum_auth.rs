@@ -0,0 +1,258 @@
+/*!
+ * Quorum Authorization
+ * Shamir secret-sharing based m-of-n operator authorization
+ */
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+/// How long a partial share buffer is kept before a fresh submission
+/// discards it, so a stale in-progress quorum can't be completed by a
+/// share submitted long after the others.
+const DEFAULT_SHARE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Prime field modulus shared by every share and every arithmetic
+/// operation performed against them. 2^61 - 1 (M61) is a Mersenne prime,
+/// chosen as the largest one whose arithmetic (products of two field
+/// elements) stays inside the `u128` / `i128` intermediates that `eval_poly`
+/// and `lagrange_interpolate_at_zero` already use, without needing a bignum
+/// crate.
+///
+/// The only persisted material is `SHA256(secret)` (see `secret_commitment`
+/// below), so the real security bound is the cost of brute-forcing a
+/// ~2^61 secret space against that commitment, not cryptographic hardness -
+/// this is not a general-purpose commitment scheme. That's still on the
+/// order of exascale SHA-256 work (vs. ~2^31, seconds on a laptop, for a
+/// 31-bit prime), which is why a commitment leak alone shouldn't be
+/// treated as equivalent to a secret leak; it is not "impossible to invert"
+/// in an absolute sense.
+pub const FIELD_PRIME: u64 = 2_305_843_009_213_693_951;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Share {
+    pub x: u64,
+    pub y: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuorumResult {
+    /// Not enough distinct shares collected yet; carries the current count.
+    Pending(usize),
+    Authorized,
+    /// Reconstruction completed but didn't match the commitment - a
+    /// forged or corrupted share is in the buffer.
+    Mismatch,
+}
+
+/// Holds only `H(secret)` - never the secret or the polynomial - and
+/// buffers operator shares until a threshold is reached.
+pub struct QuorumAuth {
+    threshold: usize,
+    total_shares: usize,
+    secret_commitment: Vec<u8>,
+    pending_shares: HashMap<u64, Share>,
+    pending_since: Option<Instant>,
+    share_timeout: Duration,
+}
+
+impl QuorumAuth {
+    /// Generates a random secret in `GF(FIELD_PRIME)`, builds a random
+    /// degree-`(threshold - 1)` polynomial with `f(0) = secret`, and
+    /// returns the `QuorumAuth` (storing only the commitment) alongside
+    /// the `total_shares` operator points `(x, f(x))`.
+    pub fn generate(threshold: usize, total_shares: usize) -> (Self, Vec<Share>) {
+        assert!(threshold >= 1, "threshold must be at least 1");
+        assert!(total_shares >= threshold, "total_shares must be >= threshold");
+
+        let mut rng = rand::thread_rng();
+        let secret: u64 = rng.gen_range(1..FIELD_PRIME);
+
+        let mut coefficients = vec![secret];
+        for _ in 1..threshold {
+            coefficients.push(rng.gen_range(0..FIELD_PRIME));
+        }
+
+        let shares = (1..=total_shares as u64)
+            .map(|x| Share { x, y: Self::eval_poly(&coefficients, x) })
+            .collect();
+
+        (
+            Self {
+                threshold,
+                total_shares,
+                secret_commitment: Self::hash_secret(secret),
+                pending_shares: HashMap::new(),
+                pending_since: None,
+                share_timeout: DEFAULT_SHARE_TIMEOUT,
+            },
+            shares,
+        )
+    }
+
+    /// Rebuilds a `QuorumAuth` from a previously captured commitment (see
+    /// `commitment_hex`) instead of generating a fresh secret. Operator
+    /// shares are handed out once, out of band, when the secret is first
+    /// generated; if the commitment were regenerated on every restart those
+    /// shares would silently stop matching it. Loading the commitment back
+    /// in lets the process restart without re-running the share ceremony.
+    pub fn from_commitment_hex(threshold: usize, total_shares: usize, commitment_hex: &str) -> Result<Self, String> {
+        assert!(threshold >= 1, "threshold must be at least 1");
+        assert!(total_shares >= threshold, "total_shares must be >= threshold");
+
+        Ok(Self {
+            threshold,
+            total_shares,
+            secret_commitment: decode_hex(commitment_hex)?,
+            pending_shares: HashMap::new(),
+            pending_since: None,
+            share_timeout: DEFAULT_SHARE_TIMEOUT,
+        })
+    }
+
+    /// Hex encoding of `H(secret)`, suitable for writing to the file an
+    /// operator points `emergency_secret_commitment_file` at so the
+    /// commitment survives a restart without the secret itself ever
+    /// touching disk.
+    pub fn commitment_hex(&self) -> String {
+        encode_hex(&self.secret_commitment)
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn total_shares(&self) -> usize {
+        self.total_shares
+    }
+
+    fn eval_poly(coefficients: &[u64], x: u64) -> u64 {
+        let p = FIELD_PRIME as u128;
+        let mut result: u128 = 0;
+        let mut x_pow: u128 = 1;
+
+        for &c in coefficients {
+            result = (result + c as u128 * x_pow) % p;
+            x_pow = (x_pow * x as u128) % p;
+        }
+
+        result as u64
+    }
+
+    fn hash_secret(secret: u64) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(secret.to_be_bytes());
+        hasher.finalize().to_vec()
+    }
+
+    /// Buffers `share` (keyed by its x-coordinate so duplicate submissions
+    /// don't count twice). Once `threshold` distinct shares are present,
+    /// reconstructs the secret via Lagrange interpolation at `x=0` and
+    /// checks it against the stored commitment, clearing the buffer
+    /// either way.
+    pub fn submit_reset_share(&mut self, operator_id: u64, share: Share) -> QuorumResult {
+        if self.pending_since.map(|t| t.elapsed() > self.share_timeout).unwrap_or(false) {
+            warn!("⏰ Pending quorum share buffer timed out; discarding stale shares");
+            self.pending_shares.clear();
+            self.pending_since = None;
+        }
+
+        if self.pending_shares.contains_key(&share.x) {
+            warn!(
+                "⚠️ Duplicate share x-coordinate {} from operator {}",
+                share.x, operator_id
+            );
+            return QuorumResult::Pending(self.pending_shares.len());
+        }
+
+        self.pending_shares.insert(share.x, share);
+        self.pending_since.get_or_insert_with(Instant::now);
+
+        if self.pending_shares.len() < self.threshold {
+            return QuorumResult::Pending(self.pending_shares.len());
+        }
+
+        let shares: Vec<Share> = self.pending_shares.values().take(self.threshold).cloned().collect();
+        let reconstructed = Self::lagrange_interpolate_at_zero(&shares);
+        let matches = Self::hash_secret(reconstructed) == self.secret_commitment;
+
+        self.pending_shares.clear();
+        self.pending_since = None;
+
+        if matches {
+            QuorumResult::Authorized
+        } else {
+            QuorumResult::Mismatch
+        }
+    }
+
+    pub fn clear_pending(&mut self) {
+        self.pending_shares.clear();
+        self.pending_since = None;
+    }
+
+    /// Lagrange interpolation of `f(0)` from `shares`, with every
+    /// arithmetic step reduced mod `FIELD_PRIME`. Requires all x-coordinates
+    /// to be distinct, which `submit_reset_share`'s dedup already enforces.
+    fn lagrange_interpolate_at_zero(shares: &[Share]) -> u64 {
+        let p = FIELD_PRIME as i128;
+        let mut secret: i128 = 0;
+
+        for (i, share_i) in shares.iter().enumerate() {
+            let mut numerator: i128 = 1;
+            let mut denominator: i128 = 1;
+
+            for (j, share_j) in shares.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+
+                numerator = (numerator * (-(share_j.x as i128))).rem_euclid(p);
+                denominator = (denominator * (share_i.x as i128 - share_j.x as i128)).rem_euclid(p);
+            }
+
+            let lagrange_coefficient = (numerator * mod_inverse(denominator, p)).rem_euclid(p);
+            secret = (secret + share_i.y as i128 * lagrange_coefficient).rem_euclid(p);
+        }
+
+        secret as u64
+    }
+}
+
+/// Modular inverse of `a` mod prime `p` via the extended Euclidean
+/// algorithm.
+fn mod_inverse(a: i128, p: i128) -> i128 {
+    let (mut old_r, mut r) = (a.rem_euclid(p), p);
+    let (mut old_s, mut s) = (1i128, 0i128);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        let new_r = old_r - quotient * r;
+        let new_s = old_s - quotient * s;
+        old_r = r;
+        r = new_r;
+        old_s = s;
+        s = new_s;
+    }
+
+    old_s.rem_euclid(p)
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, String> {
+    let s = s.trim();
+
+    if s.len() % 2 != 0 {
+        return Err("commitment hex string must have an even length".to_string());
+    }
+
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(|e| format!("invalid commitment hex: {}", e)))
+        .collect()
+}
@@ -0,0 +1,151 @@
+/*!
+ * Worker Manager
+ * Generic background-worker subsystem with state reporting and control
+ */
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, RwLock};
+use tokio::time::Duration;
+use tracing::debug;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WorkerState {
+    Active,
+    Idle,
+    Done,
+}
+
+/// A single background loop owned by the `WorkerManager`. `step` is called
+/// repeatedly until it reports `Done`; implementations that do CPU-heavy
+/// work should hop onto `tokio::task::spawn_blocking` internally so they
+/// don't starve the async runtime. An `Err` is treated as non-fatal: it's
+/// recorded in `WorkerInfo::last_error` and the loop keeps stepping, since a
+/// worker that can fail transiently (a timed-out I/O call, a lock it
+/// couldn't get) shouldn't take itself out of rotation over it.
+#[async_trait]
+pub trait BackgroundWorker: Send {
+    fn name(&self) -> &str;
+    async fn step(&mut self) -> Result<WorkerState, String>;
+}
+
+#[derive(Debug, Clone)]
+pub enum WorkerControl {
+    Start,
+    Pause,
+    Resume,
+    Cancel,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerInfo {
+    pub name: String,
+    pub state: WorkerState,
+    pub last_error: Option<String>,
+}
+
+struct WorkerHandle {
+    control_tx: mpsc::UnboundedSender<WorkerControl>,
+    info: Arc<RwLock<WorkerInfo>>,
+}
+
+/// Owns every spawned background loop, tracking each worker's last
+/// reported state and exposing a control channel to pause/resume/cancel
+/// it, so the fire-and-forget `tokio::spawn` loops in `EVARustCore::start`
+/// become observable and controllable.
+pub struct WorkerManager {
+    workers: HashMap<String, WorkerHandle>,
+}
+
+impl WorkerManager {
+    pub fn new() -> Self {
+        Self {
+            workers: HashMap::new(),
+        }
+    }
+
+    /// Spawns `worker` onto its own control loop and registers it for
+    /// `list_workers`/`control`.
+    pub fn spawn<W>(&mut self, mut worker: W) -> mpsc::UnboundedSender<WorkerControl>
+    where
+        W: BackgroundWorker + 'static,
+    {
+        let name = worker.name().to_string();
+        let (control_tx, mut control_rx) = mpsc::unbounded_channel::<WorkerControl>();
+        let info = Arc::new(RwLock::new(WorkerInfo {
+            name: name.clone(),
+            state: WorkerState::Idle,
+            last_error: None,
+        }));
+
+        let info_task = Arc::clone(&info);
+
+        tokio::spawn(async move {
+            let mut paused = false;
+
+            loop {
+                while let Ok(cmd) = control_rx.try_recv() {
+                    match cmd {
+                        WorkerControl::Pause => paused = true,
+                        WorkerControl::Resume | WorkerControl::Start => paused = false,
+                        WorkerControl::Cancel => {
+                            info_task.write().await.state = WorkerState::Done;
+                            debug!("🛑 Worker {} canceled", worker.name());
+                            return;
+                        }
+                    }
+                }
+
+                if paused {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    continue;
+                }
+
+                match worker.step().await {
+                    Ok(state) => {
+                        info_task.write().await.state = state;
+
+                        if matches!(state, WorkerState::Done) {
+                            break;
+                        }
+                    }
+                    Err(err) => {
+                        debug!("⚠️ Worker {} step failed: {}", worker.name(), err);
+                        let mut info = info_task.write().await;
+                        info.last_error = Some(err);
+                        info.state = WorkerState::Active;
+                    }
+                }
+            }
+        });
+
+        self.workers.insert(
+            name,
+            WorkerHandle {
+                control_tx: control_tx.clone(),
+                info,
+            },
+        );
+
+        control_tx
+    }
+
+    pub async fn list_workers(&self) -> Vec<WorkerInfo> {
+        let mut workers = Vec::with_capacity(self.workers.len());
+
+        for handle in self.workers.values() {
+            workers.push(handle.info.read().await.clone());
+        }
+
+        workers
+    }
+
+    pub fn control(&self, name: &str, cmd: WorkerControl) -> bool {
+        match self.workers.get(name) {
+            Some(handle) => handle.control_tx.send(cmd).is_ok(),
+            None => false,
+        }
+    }
+}